@@ -0,0 +1,299 @@
+// src-tauri/src/bpf_filter.rs
+//! A small parser for the subset of libpcap/BPF filter syntax we support
+//! (`host`/`src`/`dst`, `port`/`src port`/`dst port`, `tcp`/`udp`/`icmp`,
+//! and `and`/`or`/`not`), compiled into a predicate evaluated against each
+//! parsed IPv4 packet before it reaches `analyze_and_emit`.
+
+use pnet::packet::ip::IpNextHeaderProtocols;
+use pnet::packet::ipv4::Ipv4Packet;
+use pnet::packet::tcp::TcpPacket;
+use pnet::packet::udp::UdpPacket;
+use pnet::packet::Packet;
+use std::net::Ipv4Addr;
+
+#[derive(Debug, Clone)]
+enum Primitive {
+    Host(Ipv4Addr),
+    Src(Ipv4Addr),
+    Dst(Ipv4Addr),
+    Port(u16),
+    SrcPort(u16),
+    DstPort(u16),
+    Tcp,
+    Udp,
+    Icmp,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Primitive(Primitive),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+/// A compiled capture filter expression.
+pub struct CaptureFilter {
+    expr: Expr,
+}
+
+impl CaptureFilter {
+    /// Parses a BPF-style expression, returning an error string describing
+    /// the mistake so the UI can surface it.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let tokens: Vec<String> = input
+            .split_whitespace()
+            .map(|token| token.to_string())
+            .collect();
+        if tokens.is_empty() {
+            return Err("Filter expression is empty".to_string());
+        }
+
+        let mut parser = Parser { tokens: &tokens, pos: 0 };
+        let expr = parser.parse_or()?;
+        if parser.pos != parser.tokens.len() {
+            return Err(format!("Unexpected token: {}", parser.tokens[parser.pos]));
+        }
+
+        Ok(Self { expr })
+    }
+
+    /// Evaluates the filter against one parsed IPv4 packet.
+    pub fn matches(&self, ip_packet: &Ipv4Packet) -> bool {
+        eval(&self.expr, ip_packet)
+    }
+}
+
+struct Parser<'a> {
+    tokens: &'a [String],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(|s| s.as_str())
+    }
+
+    fn peek_lower(&self) -> Option<String> {
+        self.peek().map(|s| s.to_lowercase())
+    }
+
+    fn advance(&mut self) -> Option<&'a str> {
+        let token = self.tokens.get(self.pos).map(|s| s.as_str());
+        self.pos += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), String> {
+        match self.advance() {
+            Some(token) if token.eq_ignore_ascii_case(expected) => Ok(()),
+            Some(token) => Err(format!("Expected '{}', found '{}'", expected, token)),
+            None => Err(format!("Expected '{}', found end of expression", expected)),
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, String> {
+        let mut expr = self.parse_and()?;
+        while matches!(self.peek_lower().as_deref(), Some("or")) {
+            self.advance();
+            let rhs = self.parse_and()?;
+            expr = Expr::Or(Box::new(expr), Box::new(rhs));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, String> {
+        let mut expr = self.parse_unary()?;
+        while matches!(self.peek_lower().as_deref(), Some("and")) {
+            self.advance();
+            let rhs = self.parse_unary()?;
+            expr = Expr::And(Box::new(expr), Box::new(rhs));
+        }
+        Ok(expr)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, String> {
+        if matches!(self.peek_lower().as_deref(), Some("not")) {
+            self.advance();
+            return Ok(Expr::Not(Box::new(self.parse_unary()?)));
+        }
+        self.parse_primitive()
+    }
+
+    fn parse_primitive(&mut self) -> Result<Expr, String> {
+        let keyword = self
+            .advance()
+            .ok_or_else(|| "Expected a filter primitive".to_string())?
+            .to_lowercase();
+
+        let primitive = match keyword.as_str() {
+            "tcp" => Primitive::Tcp,
+            "udp" => Primitive::Udp,
+            "icmp" => Primitive::Icmp,
+            "host" => Primitive::Host(self.parse_ip()?),
+            "src" => {
+                match self.peek_lower().as_deref() {
+                    Some("port") => {
+                        self.advance();
+                        Primitive::SrcPort(self.parse_port()?)
+                    }
+                    _ => Primitive::Src(self.parse_ip()?),
+                }
+            }
+            "dst" => {
+                match self.peek_lower().as_deref() {
+                    Some("port") => {
+                        self.advance();
+                        Primitive::DstPort(self.parse_port()?)
+                    }
+                    _ => Primitive::Dst(self.parse_ip()?),
+                }
+            }
+            "port" => Primitive::Port(self.parse_port()?),
+            other => {
+                return Err(format!("Unknown filter primitive: {}", other));
+            }
+        };
+
+        Ok(Expr::Primitive(primitive))
+    }
+
+    fn parse_ip(&mut self) -> Result<Ipv4Addr, String> {
+        let token = self.advance().ok_or_else(|| "Expected an IP address".to_string())?;
+        token.parse::<Ipv4Addr>().map_err(|_| format!("Invalid IP address: {}", token))
+    }
+
+    fn parse_port(&mut self) -> Result<u16, String> {
+        let token = self.advance().ok_or_else(|| "Expected a port number".to_string())?;
+        token.parse::<u16>().map_err(|_| format!("Invalid port number: {}", token))
+    }
+}
+
+fn eval(expr: &Expr, ip_packet: &Ipv4Packet) -> bool {
+    match expr {
+        Expr::Primitive(primitive) => eval_primitive(primitive, ip_packet),
+        Expr::Not(inner) => !eval(inner, ip_packet),
+        Expr::And(lhs, rhs) => eval(lhs, ip_packet) && eval(rhs, ip_packet),
+        Expr::Or(lhs, rhs) => eval(lhs, ip_packet) || eval(rhs, ip_packet),
+    }
+}
+
+fn eval_primitive(primitive: &Primitive, ip_packet: &Ipv4Packet) -> bool {
+    match primitive {
+        Primitive::Host(ip) => ip_packet.get_source() == *ip || ip_packet.get_destination() == *ip,
+        Primitive::Src(ip) => ip_packet.get_source() == *ip,
+        Primitive::Dst(ip) => ip_packet.get_destination() == *ip,
+        Primitive::Port(port) => {
+            let (src_port, dst_port) = ports(ip_packet);
+            src_port == Some(*port) || dst_port == Some(*port)
+        }
+        Primitive::SrcPort(port) => ports(ip_packet).0 == Some(*port),
+        Primitive::DstPort(port) => ports(ip_packet).1 == Some(*port),
+        Primitive::Tcp => ip_packet.get_next_level_protocol() == IpNextHeaderProtocols::Tcp,
+        Primitive::Udp => ip_packet.get_next_level_protocol() == IpNextHeaderProtocols::Udp,
+        Primitive::Icmp => ip_packet.get_next_level_protocol() == IpNextHeaderProtocols::Icmp,
+    }
+}
+
+fn ports(ip_packet: &Ipv4Packet) -> (Option<u16>, Option<u16>) {
+    match ip_packet.get_next_level_protocol() {
+        IpNextHeaderProtocols::Tcp =>
+            match TcpPacket::new(ip_packet.payload()) {
+                Some(tcp_packet) => (Some(tcp_packet.get_source()), Some(tcp_packet.get_destination())),
+                None => (None, None),
+            }
+        IpNextHeaderProtocols::Udp =>
+            match UdpPacket::new(ip_packet.payload()) {
+                Some(udp_packet) => (Some(udp_packet.get_source()), Some(udp_packet.get_destination())),
+                None => (None, None),
+            }
+        _ => (None, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a minimal IPv4 packet (20-byte header, no options) carrying a
+    /// 20-byte TCP or 8-byte UDP header, enough for `CaptureFilter::matches`
+    /// to read addresses/ports/protocol from.
+    fn build_ipv4_packet(src: Ipv4Addr, dst: Ipv4Addr, protocol: u8, src_port: u16, dst_port: u16) -> Vec<u8> {
+        let transport_len: usize = if protocol == IpNextHeaderProtocols::Udp.0 { 8 } else { 20 };
+        let total_len = 20 + transport_len;
+
+        let mut packet = vec![0u8; total_len];
+        packet[0] = 0x45; // version 4, 5 * 4-byte words header
+        packet[2..4].copy_from_slice(&(total_len as u16).to_be_bytes());
+        packet[8] = 64; // TTL
+        packet[9] = protocol;
+        packet[12..16].copy_from_slice(&src.octets());
+        packet[16..20].copy_from_slice(&dst.octets());
+
+        packet[20..22].copy_from_slice(&src_port.to_be_bytes());
+        packet[22..24].copy_from_slice(&dst_port.to_be_bytes());
+        if protocol != IpNextHeaderProtocols::Udp.0 {
+            packet[32] = 0x50; // TCP data offset: 5 * 4-byte words
+        }
+
+        packet
+    }
+
+    #[test]
+    fn parses_and_matches_host_primitive() {
+        let filter = CaptureFilter::parse("host 10.0.0.1").unwrap();
+        let packet = build_ipv4_packet(
+            Ipv4Addr::new(10, 0, 0, 1),
+            Ipv4Addr::new(10, 0, 0, 2),
+            IpNextHeaderProtocols::Tcp.0,
+            1234,
+            80
+        );
+        assert!(filter.matches(&Ipv4Packet::new(&packet).unwrap()));
+
+        let other = build_ipv4_packet(
+            Ipv4Addr::new(10, 0, 0, 3),
+            Ipv4Addr::new(10, 0, 0, 4),
+            IpNextHeaderProtocols::Tcp.0,
+            1234,
+            80
+        );
+        assert!(!filter.matches(&Ipv4Packet::new(&other).unwrap()));
+    }
+
+    #[test]
+    fn parses_and_matches_compound_and_or_not_expression() {
+        let filter = CaptureFilter::parse("tcp and dst port 443").unwrap();
+        let https = build_ipv4_packet(
+            Ipv4Addr::new(10, 0, 0, 1),
+            Ipv4Addr::new(10, 0, 0, 2),
+            IpNextHeaderProtocols::Tcp.0,
+            5555,
+            443
+        );
+        assert!(filter.matches(&Ipv4Packet::new(&https).unwrap()));
+
+        let udp_same_port = build_ipv4_packet(
+            Ipv4Addr::new(10, 0, 0, 1),
+            Ipv4Addr::new(10, 0, 0, 2),
+            IpNextHeaderProtocols::Udp.0,
+            5555,
+            443
+        );
+        assert!(!filter.matches(&Ipv4Packet::new(&udp_same_port).unwrap()));
+
+        let not_udp = CaptureFilter::parse("not udp").unwrap();
+        assert!(not_udp.matches(&Ipv4Packet::new(&https).unwrap()));
+        assert!(!not_udp.matches(&Ipv4Packet::new(&udp_same_port).unwrap()));
+
+        let udp_or_icmp = CaptureFilter::parse("udp or icmp").unwrap();
+        assert!(!udp_or_icmp.matches(&Ipv4Packet::new(&https).unwrap()));
+        assert!(udp_or_icmp.matches(&Ipv4Packet::new(&udp_same_port).unwrap()));
+    }
+
+    #[test]
+    fn rejects_unknown_primitives_and_trailing_tokens() {
+        assert!(CaptureFilter::parse("bogus").is_err());
+        assert!(CaptureFilter::parse("tcp extra").is_err());
+    }
+}
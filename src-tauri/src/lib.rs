@@ -1,17 +1,35 @@
 // src-tauri/src/lib.rs
+mod bpf_filter;
+mod netinfo;
+mod pcap_writer;
+mod reassembly;
+mod server;
+
+use bpf_filter::CaptureFilter;
+use netinfo::DefaultRoute;
+use pcap_writer::PcapWriter;
+use server::RemoteServer;
+use std::net::SocketAddr;
 use pnet::datalink::{ self, Channel, Config };
-use pnet::packet::ethernet::EthernetPacket;
+use pnet::packet::arp::{ ArpOperations, ArpPacket };
+use pnet::packet::ethernet::{ EtherTypes, EthernetPacket };
 use pnet::packet::ip::IpNextHeaderProtocols;
 use pnet::packet::ipv4::Ipv4Packet;
-use pnet::packet::tcp::TcpPacket;
+use pnet::packet::ipv6::Ipv6Packet;
+use pnet::packet::tcp::{ TcpFlags, TcpPacket };
 use pnet::packet::udp::UdpPacket;
 use pnet::packet::Packet;
+use crossbeam_channel::bounded;
 use serde::Serialize;
-use std::sync::atomic::{ AtomicBool, Ordering };
-use std::sync::Arc;
+use std::sync::atomic::{ AtomicBool, AtomicU64, Ordering };
+use std::sync::{ Arc, Mutex };
 use std::time::{ SystemTime, UNIX_EPOCH };
+use reassembly::Reassembler;
 use tauri::{ Emitter, Manager, Window };
 
+const FRAME_CHANNEL_CAPACITY: usize = 4096;
+const DEFAULT_WORKER_COUNT: usize = 2;
+
 #[derive(Serialize, Clone, Debug)]
 pub struct PacketInfo {
     timestamp: String,
@@ -25,6 +43,7 @@ pub struct PacketInfo {
     sequence: Option<u32>,
     ttl: u8,
     identification: u16,
+    ip_version: u8,
 }
 
 #[derive(Serialize, Clone)]
@@ -40,38 +59,110 @@ pub struct InterfaceInfo {
     description: Option<String>,
     mac: Option<String>,
     ipv4: Vec<String>,
+    is_default: bool,
+    gateway_ip: Option<String>,
+    gateway_mac: Option<String>,
 }
 
 #[tauri::command]
 async fn list_interfaces() -> Result<Vec<InterfaceInfo>, String> {
-    let interfaces = datalink::interfaces();
-    Ok(
-        interfaces
-            .into_iter()
-            .map(|iface| InterfaceInfo {
-                name: iface.name,
-                description: Some(iface.description),
-                mac: iface.mac.map(|mac| mac.to_string()),
-                ipv4: iface.ips
-                    .iter()
-                    .filter_map(|ip| {
-                        if ip.is_ipv4() { Some(ip.to_string()) } else { None }
-                    })
-                    .collect(),
-            })
-            .collect()
-    )
+    // Default-route/gateway detection blocks on network I/O (an ICMP
+    // round trip and an `ip neigh` subprocess), so run it on a blocking
+    // thread rather than stalling the async runtime.
+    tauri::async_runtime
+        ::spawn_blocking(|| {
+            let interfaces = datalink::interfaces();
+            let default_route = netinfo::detect_default_route();
+            let (gateway_ip, gateway_mac) = match &default_route {
+                Some(route) => netinfo::detect_gateway(route.local_ip),
+                None => (None, None),
+            };
+
+            interfaces
+                .into_iter()
+                .map(|iface| {
+                    let is_default = default_route
+                        .as_ref()
+                        .map(|route| route.interface.name == iface.name)
+                        .unwrap_or(false);
+                    InterfaceInfo {
+                        name: iface.name,
+                        description: Some(iface.description),
+                        mac: iface.mac.map(|mac| mac.to_string()),
+                        ipv4: iface.ips
+                            .iter()
+                            .filter_map(|ip| {
+                                if ip.is_ipv4() { Some(ip.to_string()) } else { None }
+                            })
+                            .collect(),
+                        is_default,
+                        gateway_ip: if is_default { gateway_ip.clone() } else { None },
+                        gateway_mac: if is_default { gateway_mac.clone() } else { None },
+                    }
+                })
+                .collect::<Vec<_>>()
+        }).await
+        .map_err(|e| e.to_string())
+}
+
+/// The running flag and drop counter for whichever capture session is
+/// currently active. Held behind a `Mutex` and swapped in place on every
+/// `start_capture` call, since `Manager::manage` is a no-op for a type
+/// that's already managed — re-managing a fresh `Arc` per session would
+/// leave `stop_capture`/`dropped_packet_count` bound to the first
+/// session's counters forever.
+struct CaptureHandle {
+    running: Arc<AtomicBool>,
+    dropped_packets: Arc<AtomicU64>,
+}
+
+type CaptureHandleState = Mutex<Option<CaptureHandle>>;
+
+/// The currently running remote server, if any. Held behind a `Mutex` and
+/// swapped in place on every `start_server` call for the same reason as
+/// `CaptureHandleState`: re-managing a fresh `Arc<RemoteServer>` would leave
+/// the capture pipeline broadcasting to whichever server was managed first.
+type RemoteServerState = Mutex<Option<Arc<RemoteServer>>>;
+
+fn current_remote_server(window: &Window) -> Option<Arc<RemoteServer>> {
+    window.try_state::<RemoteServerState>()?.lock().ok()?.clone()
 }
 
 #[tauri::command]
-async fn start_capture(window: Window, interface_name: Option<String>) -> Result<(), String> {
+async fn start_capture(
+    window: Window,
+    interface_name: Option<String>,
+    output_path: Option<String>,
+    worker_count: Option<usize>,
+    filter: Option<String>
+) -> Result<(), String> {
     let running = Arc::new(AtomicBool::new(true));
+    let dropped_packets = Arc::new(AtomicU64::new(0));
     let running_clone = running.clone();
+    let dropped_packets_clone = dropped_packets.clone();
 
-    window.manage(running);
+    let handle = CaptureHandle { running, dropped_packets };
+    match window.try_state::<CaptureHandleState>() {
+        Some(state) => {
+            *state.lock().map_err(|e| e.to_string())? = Some(handle);
+        }
+        None => {
+            window.manage(Mutex::new(Some(handle)));
+        }
+    }
 
     std::thread::spawn(move || {
-        if let Err(e) = init_capture(&window, interface_name, running_clone) {
+        if
+            let Err(e) = init_capture(
+                &window,
+                interface_name,
+                output_path,
+                worker_count,
+                filter,
+                running_clone,
+                dropped_packets_clone
+            )
+        {
             let status = CaptureStatus {
                 success: false,
                 message: e,
@@ -85,16 +176,68 @@ async fn start_capture(window: Window, interface_name: Option<String>) -> Result
 }
 
 #[tauri::command]
-async fn stop_capture(state: tauri::State<'_, Arc<AtomicBool>>) -> Result<(), String> {
-    state.store(false, Ordering::SeqCst);
+async fn stop_capture(state: tauri::State<'_, CaptureHandleState>) -> Result<(), String> {
+    if let Some(handle) = state.lock().map_err(|e| e.to_string())?.as_ref() {
+        handle.running.store(false, Ordering::SeqCst);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn dropped_packet_count(state: tauri::State<'_, CaptureHandleState>) -> Result<u64, String> {
+    Ok(
+        state
+            .lock()
+            .map_err(|e| e.to_string())?
+            .as_ref()
+            .map(|handle| handle.dropped_packets.load(Ordering::Relaxed))
+            .unwrap_or(0)
+    )
+}
+
+#[tauri::command]
+async fn start_server(window: Window, port: Option<u16>) -> Result<(), String> {
+    let server = Arc::new(RemoteServer::new());
+    match window.try_state::<RemoteServerState>() {
+        Some(state) => {
+            *state.lock().map_err(|e| e.to_string())? = Some(server.clone());
+        }
+        None => {
+            window.manage(Mutex::new(Some(server.clone())));
+        }
+    }
+
+    let addr = SocketAddr::from(([0, 0, 0, 0], port.unwrap_or(7878)));
+    std::thread::spawn(move || {
+        let runtime = match tokio::runtime::Runtime::new() {
+            Ok(runtime) => runtime,
+            Err(e) => {
+                println!("Failed to start remote server runtime: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = runtime.block_on(server.run(addr)) {
+            println!("Remote server error: {}", e);
+        }
+    });
+
     Ok(())
 }
 
 fn init_capture(
     window: &Window,
     interface_name: Option<String>,
-    running: Arc<AtomicBool>
+    output_path: Option<String>,
+    worker_count: Option<usize>,
+    filter: Option<String>,
+    running: Arc<AtomicBool>,
+    dropped_packets: Arc<AtomicU64>
 ) -> Result<(), String> {
+    let capture_filter = match filter {
+        Some(expression) => Some(CaptureFilter::parse(&expression)?),
+        None => None,
+    };
+
     let interfaces = datalink::interfaces();
 
     let interface = match &interface_name {
@@ -105,10 +248,14 @@ fn init_capture(
                 .find(|iface| iface.name == *name) // Dereferencing here
                 .ok_or_else(|| "Specified interface not found".to_string())?,
         None =>
-            interfaces
-                .into_iter()
-                .find(|iface| iface.is_up() && !iface.is_loopback())
-                .ok_or_else(|| "No active network interface found".to_string())?,
+            match netinfo::detect_default_route() {
+                Some(DefaultRoute { interface, .. }) => interface,
+                None =>
+                    interfaces
+                        .into_iter()
+                        .find(|iface| iface.is_up() && !iface.is_loopback())
+                        .ok_or_else(|| "No active network interface found".to_string())?,
+            },
     };
 
     println!("Using device: {}", interface.name);
@@ -131,22 +278,73 @@ fn init_capture(
     };
     let _ = window.emit("capture-status", status);
 
-    while running.load(Ordering::SeqCst) {
-        match rx.next() {
-            Ok(packet) => {
-                if let Some(ethernet_packet) = EthernetPacket::new(packet) {
-                    if let Some(ip_packet) = Ipv4Packet::new(ethernet_packet.payload()) {
-                        let packet_info = analyze_packet(&ip_packet);
-                        if let Err(e) = window.emit("packet-captured", packet_info) {
-                            println!("Failed to emit packet info: {}", e);
-                        }
+    if let Some(remote_server) = current_remote_server(window) {
+        remote_server.set_capturing(&interface.name, true);
+    }
+
+    let pcap_writer = match output_path {
+        Some(path) => Some(PcapWriter::create(&path, &interface.name).map_err(|e| e.to_string())?),
+        None => None,
+    };
+
+    // The receiver thread does nothing but drain the datalink channel into
+    // owned buffers, so kernel-buffer drainage never waits on parsing/IPC.
+    let (frame_tx, frame_rx) = bounded::<Vec<u8>>(FRAME_CHANNEL_CAPACITY);
+    let receiver_running = running.clone();
+    let receiver_handle = std::thread::spawn(move || {
+        while receiver_running.load(Ordering::SeqCst) {
+            match rx.next() {
+                Ok(packet) => {
+                    if frame_tx.try_send(packet.to_vec()).is_err() {
+                        dropped_packets.fetch_add(1, Ordering::Relaxed);
                     }
                 }
+                Err(e) => {
+                    println!("Failed to receive packet: {}", e);
+                }
             }
-            Err(e) => {
-                println!("Failed to receive packet: {}", e);
+        }
+    });
+
+    // Reassembly and the pcap file both depend on seeing frames in capture
+    // order, so a single ordering thread does that work before handing
+    // frames to the (unordered) analysis worker pool.
+    let (analyze_tx, analyze_rx) = bounded::<Vec<u8>>(FRAME_CHANNEL_CAPACITY);
+    let order_window = window.clone();
+    let order_handle = std::thread::spawn(move || {
+        let mut pcap_writer = pcap_writer;
+        let mut reassembler = Reassembler::new();
+        for frame in frame_rx.iter() {
+            let passed_filter = order_frame(&order_window, &frame, &mut pcap_writer, &mut reassembler, &capture_filter);
+            if passed_filter && analyze_tx.send(frame).is_err() {
+                break;
             }
         }
+    });
+
+    let worker_count = worker_count.unwrap_or(DEFAULT_WORKER_COUNT).max(1);
+    let worker_handles: Vec<_> = (0..worker_count)
+        .map(|_| {
+            let analyze_rx = analyze_rx.clone();
+            let window = window.clone();
+
+            std::thread::spawn(move || {
+                for frame in analyze_rx.iter() {
+                    analyze_and_emit(&window, &frame);
+                }
+            })
+        })
+        .collect();
+    drop(analyze_rx);
+
+    let _ = receiver_handle.join();
+    let _ = order_handle.join();
+    for handle in worker_handles {
+        let _ = handle.join();
+    }
+
+    if let Some(remote_server) = current_remote_server(window) {
+        remote_server.set_capturing(&interface.name, false);
     }
 
     let status = CaptureStatus {
@@ -159,26 +357,143 @@ fn init_capture(
     Ok(())
 }
 
-fn analyze_packet(ip_packet: &Ipv4Packet) -> PacketInfo {
+/// Runs the order-sensitive work for one frame — the BPF filter, TCP
+/// reassembly feed, and the pcap file write — all on the single thread
+/// that owns `pcap_writer`/`reassembler`, so capture order is preserved.
+/// Returns `false` if the frame was filtered out and should go no further.
+fn order_frame(
+    window: &Window,
+    frame: &[u8],
+    pcap_writer: &mut Option<PcapWriter>,
+    reassembler: &mut Reassembler,
+    capture_filter: &Option<CaptureFilter>
+) -> bool {
+    let Some(ethernet_packet) = EthernetPacket::new(frame) else {
+        return false;
+    };
+
+    if ethernet_packet.get_ethertype() == EtherTypes::Ipv4 {
+        let Some(ip_packet) = Ipv4Packet::new(ethernet_packet.payload()) else {
+            return false;
+        };
+        if let Some(capture_filter) = capture_filter {
+            if !capture_filter.matches(&ip_packet) {
+                return false;
+            }
+        }
+        reassemble_tcp(window, &ip_packet, reassembler);
+    } else if capture_filter.is_some() {
+        // BPF filters only match IPv4 packets here; with a filter active,
+        // non-IPv4 frames (IPv6, ARP) can never match it and should be
+        // dropped rather than passed through unfiltered.
+        return false;
+    }
+
+    if let Some(writer) = pcap_writer {
+        if let Err(e) = writer.write_packet(0, frame) {
+            println!("Failed to write packet to pcap file: {}", e);
+        }
+    }
+
+    true
+}
+
+fn reassemble_tcp(window: &Window, ip_packet: &Ipv4Packet, reassembler: &mut Reassembler) {
+    if ip_packet.get_next_level_protocol() != IpNextHeaderProtocols::Tcp {
+        return;
+    }
+    let Some(tcp_packet) = TcpPacket::new(ip_packet.payload()) else {
+        return;
+    };
+
+    let stream_event = reassembler.process_segment(
+        ip_packet.get_source(),
+        tcp_packet.get_source(),
+        ip_packet.get_destination(),
+        tcp_packet.get_destination(),
+        tcp_packet.get_sequence(),
+        tcp_packet.get_flags() & TcpFlags::SYN != 0,
+        tcp_packet.get_flags() & TcpFlags::FIN != 0,
+        tcp_packet.get_flags() & TcpFlags::RST != 0,
+        tcp_packet.payload()
+    );
+    if let Some(stream_event) = stream_event {
+        let _ = window.emit("capture-stream", stream_event);
+    }
+}
+
+/// Parses a frame that already passed the filter/reassembly stage and
+/// emits/broadcasts its `PacketInfo`. Order-independent — safe to run
+/// concurrently across the worker pool.
+fn analyze_and_emit(window: &Window, frame: &[u8]) {
+    let Some(ethernet_packet) = EthernetPacket::new(frame) else {
+        return;
+    };
+
+    let packet_info = match ethernet_packet.get_ethertype() {
+        EtherTypes::Ipv4 => {
+            let Some(ip_packet) = Ipv4Packet::new(ethernet_packet.payload()) else {
+                return;
+            };
+            analyze_ipv4_packet(&ip_packet)
+        }
+        EtherTypes::Ipv6 => {
+            let Some(ip_packet) = Ipv6Packet::new(ethernet_packet.payload()) else {
+                return;
+            };
+            analyze_ipv6_packet(&ip_packet)
+        }
+        EtherTypes::Arp => {
+            let Some(arp_packet) = ArpPacket::new(ethernet_packet.payload()) else {
+                return;
+            };
+            analyze_arp_packet(&arp_packet)
+        }
+        _ => {
+            return;
+        }
+    };
+
+    if let Some(remote_server) = current_remote_server(window) {
+        remote_server.broadcast_packet(&packet_info);
+    }
+    if let Err(e) = window.emit("packet-captured", packet_info) {
+        println!("Failed to emit packet info: {}", e);
+    }
+}
+
+fn empty_packet_info(ip_version: u8, length: usize, source: String, destination: String, ttl: u8) -> PacketInfo {
     let timestamp = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap_or_default()
         .as_secs()
         .to_string();
 
-    let mut packet_info = PacketInfo {
+    PacketInfo {
         timestamp,
-        length: ip_packet.packet().len(),
+        length,
         protocol: "Unknown".to_string(),
-        source: ip_packet.get_source().to_string(),
-        destination: ip_packet.get_destination().to_string(),
+        source,
+        destination,
         source_port: None,
         dest_port: None,
         flags: None,
         sequence: None,
-        ttl: ip_packet.get_ttl(),
-        identification: ip_packet.get_identification(),
-    };
+        ttl,
+        identification: 0,
+        ip_version,
+    }
+}
+
+fn analyze_ipv4_packet(ip_packet: &Ipv4Packet) -> PacketInfo {
+    let mut packet_info = empty_packet_info(
+        4,
+        ip_packet.packet().len(),
+        ip_packet.get_source().to_string(),
+        ip_packet.get_destination().to_string(),
+        ip_packet.get_ttl()
+    );
+    packet_info.identification = ip_packet.get_identification();
 
     match ip_packet.get_next_level_protocol() {
         IpNextHeaderProtocols::Tcp => {
@@ -216,13 +531,94 @@ fn analyze_packet(ip_packet: &Ipv4Packet) -> PacketInfo {
     packet_info
 }
 
+fn analyze_ipv6_packet(ip_packet: &Ipv6Packet) -> PacketInfo {
+    let mut packet_info = empty_packet_info(
+        6,
+        ip_packet.packet().len(),
+        ip_packet.get_source().to_string(),
+        ip_packet.get_destination().to_string(),
+        ip_packet.get_hop_limit()
+    );
+
+    match ip_packet.get_next_header() {
+        IpNextHeaderProtocols::Tcp => {
+            if let Some(tcp_packet) = TcpPacket::new(ip_packet.payload()) {
+                packet_info.protocol = "TCP".to_string();
+                packet_info.source_port = Some(tcp_packet.get_source());
+                packet_info.dest_port = Some(tcp_packet.get_destination());
+                packet_info.flags = Some(
+                    format!(
+                        "SYN:{} ACK:{} FIN:{} RST:{}",
+                        (tcp_packet.get_flags() & 0b10) != 0,
+                        (tcp_packet.get_flags() & 0b10000) != 0,
+                        (tcp_packet.get_flags() & 0b1) != 0,
+                        (tcp_packet.get_flags() & 0b100) != 0
+                    )
+                );
+                packet_info.sequence = Some(tcp_packet.get_sequence());
+            }
+        }
+        IpNextHeaderProtocols::Udp => {
+            if let Some(udp_packet) = UdpPacket::new(ip_packet.payload()) {
+                packet_info.protocol = "UDP".to_string();
+                packet_info.source_port = Some(udp_packet.get_source());
+                packet_info.dest_port = Some(udp_packet.get_destination());
+            }
+        }
+        IpNextHeaderProtocols::Icmpv6 => {
+            packet_info.protocol = "ICMPv6".to_string();
+        }
+        other => {
+            packet_info.protocol = format!("Other({})", other.0);
+        }
+    }
+
+    packet_info
+}
+
+fn analyze_arp_packet(arp_packet: &ArpPacket) -> PacketInfo {
+    let operation = if arp_packet.get_operation() == ArpOperations::Request {
+        "ARP Request"
+    } else if arp_packet.get_operation() == ArpOperations::Reply {
+        "ARP Reply"
+    } else {
+        "ARP"
+    };
+
+    let mut packet_info = empty_packet_info(
+        0,
+        arp_packet.packet().len(),
+        arp_packet.get_sender_proto_addr().to_string(),
+        arp_packet.get_target_proto_addr().to_string(),
+        0
+    );
+    packet_info.protocol = operation.to_string();
+    packet_info.flags = Some(
+        format!(
+            "sender_mac:{} target_mac:{}",
+            arp_packet.get_sender_hw_addr(),
+            arp_packet.get_target_hw_addr()
+        )
+    );
+
+    packet_info
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     let mut builder = tauri::Builder
         ::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_notification::init())
-        .invoke_handler(tauri::generate_handler![start_capture, stop_capture, list_interfaces]);
+        .invoke_handler(
+            tauri::generate_handler![
+                start_capture,
+                stop_capture,
+                list_interfaces,
+                dropped_packet_count,
+                start_server
+            ]
+        );
 
     #[cfg(desktop)]
     {
@@ -0,0 +1,72 @@
+// src-tauri/src/netinfo.rs
+//! Detects the host's default route so `init_capture` can pick the right
+//! interface on multi-homed hosts instead of guessing the first one up.
+
+use pnet::datalink::{ self, NetworkInterface };
+use pnet::packet::icmp::IcmpTypes;
+use pnet::packet::ip::IpNextHeaderProtocols;
+use pnet::transport::{ icmp_packet_iter, transport_channel, TransportChannelType, TransportProtocol };
+use std::net::{ IpAddr, Ipv4Addr, UdpSocket };
+use std::time::Duration;
+
+pub struct DefaultRoute {
+    pub interface: NetworkInterface,
+    pub local_ip: Ipv4Addr,
+}
+
+/// Finds the interface the kernel would use to reach the public internet by
+/// opening a connected UDP socket (no packets are actually sent) and reading
+/// back the source address it bound to.
+pub fn detect_default_route() -> Option<DefaultRoute> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("8.8.8.8:80").ok()?;
+    let local_ip = match socket.local_addr().ok()?.ip() {
+        IpAddr::V4(ip) => ip,
+        IpAddr::V6(_) => {
+            return None;
+        }
+    };
+
+    let interface = datalink::interfaces()
+        .into_iter()
+        .find(|iface| iface.ips.iter().any(|ip| ip.ip() == IpAddr::V4(local_ip)))?;
+
+    Some(DefaultRoute { interface, local_ip })
+}
+
+/// Resolves the default gateway by sending a TTL-1 UDP probe and capturing
+/// the ICMP Time-Exceeded reply, then looking up its MAC via the neighbor
+/// table.
+pub fn detect_gateway(local_ip: Ipv4Addr) -> (Option<String>, Option<String>) {
+    let gateway_ip = probe_gateway_ip(local_ip);
+    let gateway_mac = gateway_ip.as_ref().and_then(|ip| resolve_neighbor_mac(ip));
+    (gateway_ip, gateway_mac)
+}
+
+fn probe_gateway_ip(local_ip: Ipv4Addr) -> Option<String> {
+    let (_, mut icmp_rx) = transport_channel(
+        4096,
+        TransportChannelType::Layer4(TransportProtocol::Ipv4(IpNextHeaderProtocols::Icmp))
+    ).ok()?;
+
+    let probe_socket = UdpSocket::bind((local_ip, 0)).ok()?;
+    probe_socket.set_ttl(1).ok()?;
+    probe_socket.send_to(&[0u8; 8], "8.8.8.8:33434").ok()?;
+
+    let mut replies = icmp_packet_iter(&mut icmp_rx);
+    match replies.next_with_timeout(Duration::from_secs(1)) {
+        Ok(Some((packet, addr))) if packet.get_icmp_type() == IcmpTypes::TimeExceeded => {
+            Some(addr.to_string())
+        }
+        _ => None,
+    }
+}
+
+fn resolve_neighbor_mac(ip: &str) -> Option<String> {
+    let output = std::process::Command::new("ip").args(["neigh", "show", ip]).output().ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    text.split_whitespace()
+        .skip_while(|token| *token != "lladdr")
+        .nth(1)
+        .map(|mac| mac.to_string())
+}
@@ -0,0 +1,163 @@
+// src-tauri/src/pcap_writer.rs
+//! Minimal PCAP-NG writer so raw captures can be reopened in Wireshark/tshark.
+
+use std::fs::File;
+use std::io::{ self, Write };
+use std::time::{ SystemTime, UNIX_EPOCH };
+
+const SECTION_HEADER_BLOCK_TYPE: u32 = 0x0a0d0d0a;
+const BYTE_ORDER_MAGIC: u32 = 0x1a2b3c4d;
+const INTERFACE_DESCRIPTION_BLOCK_TYPE: u32 = 0x00000001;
+const ENHANCED_PACKET_BLOCK_TYPE: u32 = 0x00000006;
+const LINKTYPE_ETHERNET: u16 = 1;
+
+const OPT_IF_NAME: u16 = 2;
+const OPT_IF_TSRESOL: u16 = 9;
+const OPT_END_OF_OPT: u16 = 0;
+
+/// Writes capture frames to disk as a PCAP-NG file, one interface per writer.
+pub struct PcapWriter {
+    file: File,
+}
+
+impl PcapWriter {
+    /// Creates `path`, writing the Section Header Block and a single
+    /// Interface Description Block for `interface_name` up front.
+    pub fn create(path: &str, interface_name: &str) -> io::Result<Self> {
+        let mut file = File::create(path)?;
+        write_section_header_block(&mut file)?;
+        write_interface_description_block(&mut file, interface_name)?;
+        Ok(Self { file })
+    }
+
+    /// Appends one Enhanced Packet Block for a raw Ethernet frame captured
+    /// on interface `interface_id` (index into the IDBs written so far).
+    pub fn write_packet(&mut self, interface_id: u32, frame: &[u8]) -> io::Result<()> {
+        write_enhanced_packet_block(&mut self.file, interface_id, frame)
+    }
+}
+
+fn write_section_header_block(file: &mut File) -> io::Result<()> {
+    let mut body = Vec::new();
+    body.extend_from_slice(&BYTE_ORDER_MAGIC.to_le_bytes());
+    body.extend_from_slice(&1u16.to_le_bytes()); // major version
+    body.extend_from_slice(&0u16.to_le_bytes()); // minor version
+    body.extend_from_slice(&(-1i64).to_le_bytes()); // section length, unknown
+    write_block(file, SECTION_HEADER_BLOCK_TYPE, &body)
+}
+
+fn write_interface_description_block(file: &mut File, interface_name: &str) -> io::Result<()> {
+    let mut body = Vec::new();
+    body.extend_from_slice(&LINKTYPE_ETHERNET.to_le_bytes());
+    body.extend_from_slice(&0u16.to_le_bytes()); // reserved
+    body.extend_from_slice(&0u32.to_le_bytes()); // snaplen, 0 = no limit
+
+    write_option(&mut body, OPT_IF_NAME, interface_name.as_bytes());
+    write_option(&mut body, OPT_IF_TSRESOL, &[6u8]); // microsecond resolution
+    body.extend_from_slice(&OPT_END_OF_OPT.to_le_bytes());
+    body.extend_from_slice(&0u16.to_le_bytes()); // end-of-options length
+
+    write_block(file, INTERFACE_DESCRIPTION_BLOCK_TYPE, &body)
+}
+
+fn write_enhanced_packet_block(file: &mut File, interface_id: u32, frame: &[u8]) -> io::Result<()> {
+    let micros = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_micros() as u64;
+    let timestamp_high = (micros >> 32) as u32;
+    let timestamp_low = micros as u32;
+
+    let mut body = Vec::new();
+    body.extend_from_slice(&interface_id.to_le_bytes());
+    body.extend_from_slice(&timestamp_high.to_le_bytes());
+    body.extend_from_slice(&timestamp_low.to_le_bytes());
+    body.extend_from_slice(&(frame.len() as u32).to_le_bytes()); // captured length
+    body.extend_from_slice(&(frame.len() as u32).to_le_bytes()); // original length
+    body.extend_from_slice(frame);
+    body.resize(body.len() + padding_len(frame.len()), 0);
+
+    write_block(file, ENHANCED_PACKET_BLOCK_TYPE, &body)
+}
+
+fn write_option(body: &mut Vec<u8>, code: u16, value: &[u8]) {
+    body.extend_from_slice(&code.to_le_bytes());
+    body.extend_from_slice(&(value.len() as u16).to_le_bytes());
+    body.extend_from_slice(value);
+    body.resize(body.len() + padding_len(value.len()), 0);
+}
+
+fn padding_len(len: usize) -> usize {
+    (4 - (len % 4)) % 4
+}
+
+fn write_block(file: &mut File, block_type: u32, body: &[u8]) -> io::Result<()> {
+    // Block Total Length is repeated at the start and end of the block.
+    let total_len = (body.len() + 12) as u32;
+    file.write_all(&block_type.to_le_bytes())?;
+    file.write_all(&total_len.to_le_bytes())?;
+    file.write_all(body)?;
+    file.write_all(&total_len.to_le_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir().join(format!("pcap_writer_test_{}_{}.pcapng", name, std::process::id())).to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn written_file_starts_with_a_section_header_and_interface_description_block() {
+        let path = temp_path("header");
+        PcapWriter::create(&path, "eth0").unwrap();
+        let bytes = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(u32::from_le_bytes(bytes[0..4].try_into().unwrap()), SECTION_HEADER_BLOCK_TYPE);
+        let shb_len = u32::from_le_bytes(bytes[4..8].try_into().unwrap()) as usize;
+        assert_eq!(
+            u32::from_le_bytes(bytes[8..12].try_into().unwrap()),
+            BYTE_ORDER_MAGIC
+        );
+
+        let idb_offset = shb_len;
+        assert_eq!(
+            u32::from_le_bytes(bytes[idb_offset..idb_offset + 4].try_into().unwrap()),
+            INTERFACE_DESCRIPTION_BLOCK_TYPE
+        );
+        let linktype = u16::from_le_bytes(bytes[idb_offset + 8..idb_offset + 10].try_into().unwrap());
+        assert_eq!(linktype, LINKTYPE_ETHERNET);
+    }
+
+    #[test]
+    fn enhanced_packet_block_round_trips_the_frame_bytes() {
+        let path = temp_path("packet");
+        let mut writer = PcapWriter::create(&path, "eth0").unwrap();
+        let frame = vec![0xAAu8, 0xBB, 0xCC, 0xDD, 0xEE];
+        writer.write_packet(0, &frame).unwrap();
+        drop(writer);
+
+        let bytes = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        // The Enhanced Packet Block is the last block in the file; its
+        // length is padded to a 4-byte boundary, so walk back from the end
+        // using the trailing Block Total Length field.
+        let trailing_len = u32::from_le_bytes(bytes[bytes.len() - 4..].try_into().unwrap()) as usize;
+        let epb_offset = bytes.len() - trailing_len;
+
+        assert_eq!(
+            u32::from_le_bytes(bytes[epb_offset..epb_offset + 4].try_into().unwrap()),
+            ENHANCED_PACKET_BLOCK_TYPE
+        );
+        let captured_len = u32::from_le_bytes(bytes[epb_offset + 20..epb_offset + 24].try_into().unwrap()) as usize;
+        assert_eq!(captured_len, frame.len());
+
+        let frame_start = epb_offset + 28;
+        assert_eq!(&bytes[frame_start..frame_start + frame.len()], &frame[..]);
+    }
+}
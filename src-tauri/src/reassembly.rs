@@ -0,0 +1,235 @@
+// src-tauri/src/reassembly.rs
+//! Groups TCP segments into bidirectional flows and reassembles each
+//! direction's byte stream so callers get a conversation, not isolated
+//! segments.
+
+use serde::Serialize;
+use std::collections::{ BTreeMap, HashMap };
+use std::net::Ipv4Addr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct FlowKey {
+    low_ip: Ipv4Addr,
+    low_port: u16,
+    high_ip: Ipv4Addr,
+    high_port: u16,
+}
+
+impl FlowKey {
+    fn new(src_ip: Ipv4Addr, src_port: u16, dst_ip: Ipv4Addr, dst_port: u16) -> (Self, bool) {
+        if (src_ip, src_port) <= (dst_ip, dst_port) {
+            let key = Self { low_ip: src_ip, low_port: src_port, high_ip: dst_ip, high_port: dst_port };
+            (key, true)
+        } else {
+            let key = Self { low_ip: dst_ip, low_port: dst_port, high_ip: src_ip, high_port: src_port };
+            (key, false)
+        }
+    }
+
+    fn id(&self) -> String {
+        format!("tcp:{}:{}-{}:{}", self.low_ip, self.low_port, self.high_ip, self.high_port)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ConnectionState {
+    SynSent,
+    Established,
+    Closing,
+    Closed,
+}
+
+struct DirectionState {
+    next_seq: Option<u32>,
+    buffered: BTreeMap<u32, Vec<u8>>,
+}
+
+impl DirectionState {
+    fn new() -> Self {
+        Self { next_seq: None, buffered: BTreeMap::new() }
+    }
+
+    /// Feeds one segment into this direction and returns any newly
+    /// contiguous bytes (the segment itself plus any buffered segments it
+    /// unblocks), handling 32-bit sequence wraparound.
+    fn ingest(&mut self, seq: u32, syn: bool, payload: &[u8]) -> Vec<u8> {
+        if syn {
+            // The SYN itself consumes one sequence number.
+            self.next_seq = Some(seq.wrapping_add(1));
+            return Vec::new();
+        }
+
+        if payload.is_empty() {
+            return Vec::new();
+        }
+
+        let next_seq = *self.next_seq.get_or_insert(seq);
+
+        if (seq.wrapping_sub(next_seq) as i32) < 0 {
+            // Already delivered (a retransmit of old data); buffering it
+            // would leave a dead entry the drain loop can never reach,
+            // since `cursor` only ever advances forward.
+            return Vec::new();
+        }
+
+        if seq != next_seq {
+            self.buffered.insert(seq, payload.to_vec());
+            return Vec::new();
+        }
+
+        let mut reassembled = payload.to_vec();
+        let mut cursor = next_seq.wrapping_add(payload.len() as u32);
+        while let Some(chunk) = self.buffered.remove(&cursor) {
+            cursor = cursor.wrapping_add(chunk.len() as u32);
+            reassembled.extend_from_slice(&chunk);
+        }
+        self.next_seq = Some(cursor);
+        reassembled
+    }
+}
+
+struct Flow {
+    state: ConnectionState,
+    initiator: DirectionState,
+    responder: DirectionState,
+    initiator_fin: bool,
+    responder_fin: bool,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct StreamEvent {
+    pub flow_id: String,
+    pub state: ConnectionState,
+    pub from_initiator: bool,
+    pub payload: Vec<u8>,
+}
+
+/// Tracks in-flight TCP flows and reassembles their payload streams.
+pub struct Reassembler {
+    flows: HashMap<FlowKey, Flow>,
+}
+
+impl Reassembler {
+    pub fn new() -> Self {
+        Self { flows: HashMap::new() }
+    }
+
+    /// Processes one TCP segment, returning a `StreamEvent` when it carries
+    /// new reassembled payload or a connection-state transition worth
+    /// surfacing to the frontend.
+    pub fn process_segment(
+        &mut self,
+        src_ip: Ipv4Addr,
+        src_port: u16,
+        dst_ip: Ipv4Addr,
+        dst_port: u16,
+        seq: u32,
+        syn: bool,
+        fin: bool,
+        rst: bool,
+        payload: &[u8]
+    ) -> Option<StreamEvent> {
+        let (key, from_initiator) = FlowKey::new(src_ip, src_port, dst_ip, dst_port);
+        let flow = self.flows.entry(key).or_insert_with(|| Flow {
+            state: ConnectionState::SynSent,
+            initiator: DirectionState::new(),
+            responder: DirectionState::new(),
+            initiator_fin: false,
+            responder_fin: false,
+        });
+
+        let direction = if from_initiator { &mut flow.initiator } else { &mut flow.responder };
+        let payload = direction.ingest(seq, syn, payload);
+
+        let previous_state = flow.state;
+        if flow.state == ConnectionState::SynSent && !syn {
+            flow.state = ConnectionState::Established;
+        }
+        if from_initiator {
+            flow.initiator_fin = flow.initiator_fin || fin;
+        } else {
+            flow.responder_fin = flow.responder_fin || fin;
+        }
+        if rst {
+            flow.state = ConnectionState::Closed;
+        } else if flow.initiator_fin && flow.responder_fin {
+            // Both sides have sent their FIN: the teardown is complete.
+            flow.state = ConnectionState::Closed;
+        } else if fin && flow.state != ConnectionState::Closed {
+            flow.state = ConnectionState::Closing;
+        }
+
+        if payload.is_empty() && flow.state == previous_state {
+            return None;
+        }
+
+        let state = flow.state;
+        if state == ConnectionState::Closed {
+            self.flows.remove(&key);
+        }
+
+        Some(StreamEvent { flow_id: key.id(), state, from_initiator, payload })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIENT: Ipv4Addr = Ipv4Addr::new(10, 0, 0, 1);
+    const SERVER: Ipv4Addr = Ipv4Addr::new(10, 0, 0, 2);
+
+    #[test]
+    fn out_of_order_segments_reassemble_once_the_gap_is_filled() {
+        let mut reassembler = Reassembler::new();
+        reassembler.process_segment(CLIENT, 1234, SERVER, 80, 0, true, false, false, &[]);
+
+        let held_back = reassembler.process_segment(
+            CLIENT, 1234, SERVER, 80, 6, false, false, false, b"world"
+        );
+        assert!(held_back.unwrap().payload.is_empty());
+
+        let event = reassembler
+            .process_segment(CLIENT, 1234, SERVER, 80, 1, false, false, false, b"hello")
+            .unwrap();
+        assert_eq!(event.payload, b"helloworld");
+    }
+
+    #[test]
+    fn retransmitted_old_segments_are_dropped_not_buffered() {
+        let mut reassembler = Reassembler::new();
+        reassembler.process_segment(CLIENT, 1234, SERVER, 80, 0, true, false, false, &[]);
+        reassembler.process_segment(CLIENT, 1234, SERVER, 80, 1, false, false, false, b"hello");
+
+        // A retransmit of the segment already delivered above.
+        let event = reassembler.process_segment(CLIENT, 1234, SERVER, 80, 1, false, false, false, b"hello");
+        assert!(event.is_none());
+    }
+
+    #[test]
+    fn graceful_fin_from_both_sides_reaches_closed() {
+        let mut reassembler = Reassembler::new();
+        reassembler.process_segment(CLIENT, 1234, SERVER, 80, 0, true, false, false, &[]);
+
+        let after_client_fin = reassembler
+            .process_segment(CLIENT, 1234, SERVER, 80, 1, false, true, false, &[])
+            .unwrap();
+        assert_eq!(after_client_fin.state, ConnectionState::Closing);
+
+        let after_server_fin = reassembler
+            .process_segment(SERVER, 80, CLIENT, 1234, 0, false, true, false, &[])
+            .unwrap();
+        assert_eq!(after_server_fin.state, ConnectionState::Closed);
+    }
+
+    #[test]
+    fn rst_closes_the_flow_immediately() {
+        let mut reassembler = Reassembler::new();
+        reassembler.process_segment(CLIENT, 1234, SERVER, 80, 0, true, false, false, &[]);
+
+        let event = reassembler
+            .process_segment(CLIENT, 1234, SERVER, 80, 1, false, false, true, &[])
+            .unwrap();
+        assert_eq!(event.state, ConnectionState::Closed);
+    }
+}
@@ -0,0 +1,100 @@
+// src-tauri/src/server.rs
+//! An embedded HTTP/WebSocket server that mirrors the capture's
+//! `PacketInfo` stream so a remote/headless machine can be monitored from
+//! a browser on another host.
+
+use crate::PacketInfo;
+use axum::extract::ws::{ Message, WebSocket, WebSocketUpgrade };
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{ Json, Router };
+use serde::Serialize;
+use std::net::SocketAddr;
+use std::sync::atomic::{ AtomicBool, AtomicU64, Ordering };
+use std::sync::{ Arc, Mutex };
+use tokio::net::TcpListener;
+use tokio::sync::broadcast;
+
+const PACKET_CHANNEL_CAPACITY: usize = 1024;
+
+#[derive(Serialize)]
+struct StatusResponse {
+    interface: Option<String>,
+    packets: u64,
+    bytes: u64,
+    capturing: bool,
+}
+
+/// Shared state behind the remote server: live counters plus a broadcast
+/// channel fanning out every captured `PacketInfo` to connected websockets.
+pub struct RemoteServer {
+    interface_name: Mutex<Option<String>>,
+    packet_count: AtomicU64,
+    byte_count: AtomicU64,
+    capturing: AtomicBool,
+    packet_tx: broadcast::Sender<PacketInfo>,
+}
+
+impl RemoteServer {
+    pub fn new() -> Self {
+        let (packet_tx, _) = broadcast::channel(PACKET_CHANNEL_CAPACITY);
+        Self {
+            interface_name: Mutex::new(None),
+            packet_count: AtomicU64::new(0),
+            byte_count: AtomicU64::new(0),
+            capturing: AtomicBool::new(false),
+            packet_tx,
+        }
+    }
+
+    pub fn set_capturing(&self, interface_name: &str, capturing: bool) {
+        *self.interface_name.lock().unwrap() = Some(interface_name.to_string());
+        self.capturing.store(capturing, Ordering::Relaxed);
+    }
+
+    /// Records a captured packet and broadcasts it to all connected clients.
+    pub fn broadcast_packet(&self, packet: &PacketInfo) {
+        self.packet_count.fetch_add(1, Ordering::Relaxed);
+        self.byte_count.fetch_add(packet.length as u64, Ordering::Relaxed);
+        let _ = self.packet_tx.send(packet.clone());
+    }
+
+    pub async fn run(self: Arc<Self>, addr: SocketAddr) -> std::io::Result<()> {
+        let app = Router::new()
+            .route("/status", get(status_handler))
+            .route("/ws", get(ws_handler))
+            .with_state(self);
+
+        let listener = TcpListener::bind(addr).await?;
+        axum::serve(listener, app).await
+    }
+}
+
+async fn status_handler(State(server): State<Arc<RemoteServer>>) -> impl IntoResponse {
+    Json(StatusResponse {
+        interface: server.interface_name.lock().unwrap().clone(),
+        packets: server.packet_count.load(Ordering::Relaxed),
+        bytes: server.byte_count.load(Ordering::Relaxed),
+        capturing: server.capturing.load(Ordering::Relaxed),
+    })
+}
+
+async fn ws_handler(
+    State(server): State<Arc<RemoteServer>>,
+    upgrade: WebSocketUpgrade
+) -> impl IntoResponse {
+    upgrade.on_upgrade(move |socket| stream_packets(socket, server))
+}
+
+async fn stream_packets(mut socket: WebSocket, server: Arc<RemoteServer>) {
+    let mut packets = server.packet_tx.subscribe();
+    while let Ok(packet) = packets.recv().await {
+        let Ok(json) = serde_json::to_string(&packet) else {
+            continue;
+        };
+        if socket.send(Message::Text(json)).await.is_err() {
+            break;
+        }
+    }
+}